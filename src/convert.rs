@@ -0,0 +1,202 @@
+//! Round-trip between the SDK base64 format and standard PKCS#1 / PKCS#8 PEM.
+
+use anyhow::{bail, Context, Result};
+use clap::ValueEnum;
+use pkcs1::{DecodeRsaPrivateKey, DecodeRsaPublicKey, EncodeRsaPrivateKey, EncodeRsaPublicKey};
+use pkcs8::{DecodePrivateKey, DecodePublicKey, EncodePrivateKey, EncodePublicKey, LineEnding};
+use rsa::{RsaPrivateKey, RsaPublicKey};
+use rsa::traits::{PrivateKeyParts, PublicKeyParts};
+
+use crate::sdk;
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ConvertTo {
+    /// Standard PKCS#1 PEM (`RSA PRIVATE/PUBLIC KEY`)
+    Pkcs1,
+    /// Standard PKCS#8 PEM (`PRIVATE/PUBLIC KEY`)
+    Pkcs8,
+    /// This tool's SDK base64 format
+    Sdk,
+}
+
+pub fn convert(keyname: &str, to: ConvertTo, pub_: bool, pri: bool) -> Result<()> {
+    match to {
+        ConvertTo::Pkcs1 => sdk_to_pem(keyname, ConvertTo::Pkcs1, pub_, pri),
+        ConvertTo::Pkcs8 => sdk_to_pem(keyname, ConvertTo::Pkcs8, pub_, pri),
+        ConvertTo::Sdk => pem_to_sdk(keyname),
+    }
+}
+
+fn sdk_to_pem(keyname: &str, to: ConvertTo, pub_: bool, pri: bool) -> Result<()> {
+    let out_path = format!("{}.pem", keyname);
+
+    let want_private = match (pri, pub_) {
+        (true, true) => bail!("specify only one of --pri or --pub"),
+        (true, false) => true,
+        (false, true) => false,
+        (false, false) => bail!("specify --pri or --pub to choose which key to convert"),
+    };
+
+    if want_private {
+        let key = sdk::load_private(keyname)?;
+        let pem = match to {
+            ConvertTo::Pkcs1 => key.to_pkcs1_pem(LineEnding::LF)?.to_string(),
+            ConvertTo::Pkcs8 => key.to_pkcs8_pem(LineEnding::LF)?.to_string(),
+            ConvertTo::Sdk => unreachable!(),
+        };
+        std::fs::write(&out_path, pem)?;
+        println!("Wrote {} private key: {}", label(to), out_path);
+    } else {
+        let key = sdk::load_public(keyname)?;
+        let pem = match to {
+            ConvertTo::Pkcs1 => key.to_pkcs1_pem(LineEnding::LF)?,
+            ConvertTo::Pkcs8 => key.to_public_key_pem(LineEnding::LF)?,
+            ConvertTo::Sdk => unreachable!(),
+        };
+        std::fs::write(&out_path, pem)?;
+        println!("Wrote {} public key: {}", label(to), out_path);
+    }
+
+    Ok(())
+}
+
+fn pem_to_sdk(keyname: &str) -> Result<()> {
+    let pem_path = format!("{}.pem", keyname);
+    let contents = std::fs::read_to_string(&pem_path).with_context(|| format!("reading {}", pem_path))?;
+
+    if let Ok(key) = RsaPrivateKey::from_pkcs1_pem(&contents).or_else(|_| RsaPrivateKey::from_pkcs8_pem(&contents)) {
+        sdk::require_default_exponent(key.e())?;
+        let pri = sdk::encode_sdk(sdk::private_label(), key.d(), key.n());
+        let pub_ = sdk::encode_public_sdk(key.e(), key.n());
+        std::fs::write(format!("{}.pri", keyname), pri)?;
+        std::fs::write(format!("{}.pub", keyname), pub_)?;
+        println!("Wrote SDK key pair: {0}.pri, {0}.pub", keyname);
+        return Ok(());
+    }
+
+    let key = RsaPublicKey::from_pkcs1_pem(&contents)
+        .or_else(|_| RsaPublicKey::from_public_key_pem(&contents))
+        .context("PEM file is not a recognizable RSA public or private key")?;
+    let pub_ = sdk::encode_public_sdk(key.e(), key.n());
+    std::fs::write(format!("{}.pub", keyname), pub_)?;
+    println!("Wrote SDK public key: {}.pub", keyname);
+    Ok(())
+}
+
+/// Parse an arbitrary PEM or DER key file (PKCS#1 or PKCS#8, public or
+/// private) and print the equivalent SDK base64 blob. Pass `write = true`
+/// to also save it as `keyname.pri`/`keyname.pub`.
+pub fn import_foreign(keyname: &str, path: &str, write: bool) -> Result<()> {
+    let raw = std::fs::read(path).with_context(|| format!("reading {}", path))?;
+    let text = String::from_utf8(raw.clone()).ok();
+
+    if let Some(key) = text
+        .as_deref()
+        .and_then(|t| RsaPrivateKey::from_pkcs1_pem(t).or_else(|_| RsaPrivateKey::from_pkcs8_pem(t)).ok())
+        .or_else(|| RsaPrivateKey::from_pkcs1_der(&raw).or_else(|_| RsaPrivateKey::from_pkcs8_der(&raw)).ok())
+    {
+        sdk::require_default_exponent(key.e())?;
+        let pri = sdk::encode_sdk(sdk::private_label(), key.d(), key.n());
+        let pub_ = sdk::encode_public_sdk(key.e(), key.n());
+        println!("{}", pri);
+        println!("{}", pub_);
+        if write {
+            std::fs::write(format!("{}.pri", keyname), &pri)?;
+            std::fs::write(format!("{}.pub", keyname), &pub_)?;
+            println!("Wrote {0}.pri, {0}.pub", keyname);
+        }
+        return Ok(());
+    }
+
+    let key = text
+        .as_deref()
+        .and_then(|t| RsaPublicKey::from_pkcs1_pem(t).or_else(|_| RsaPublicKey::from_public_key_pem(t)).ok())
+        .or_else(|| RsaPublicKey::from_pkcs1_der(&raw).or_else(|_| RsaPublicKey::from_public_key_der(&raw)).ok())
+        .context("not a recognizable RSA public or private key (PEM or DER, PKCS#1 or PKCS#8)")?;
+
+    let pub_ = sdk::encode_public_sdk(key.e(), key.n());
+    println!("{}", pub_);
+    if write {
+        std::fs::write(format!("{}.pub", keyname), &pub_)?;
+        println!("Wrote {}.pub", keyname);
+    }
+    Ok(())
+}
+
+fn label(to: ConvertTo) -> &'static str {
+    match to {
+        ConvertTo::Pkcs1 => "PKCS#1",
+        ConvertTo::Pkcs8 => "PKCS#8",
+        ConvertTo::Sdk => "SDK",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::unique_keyname;
+
+    #[test]
+    fn pem_to_sdk_round_trip() {
+        let mut rng = rand::thread_rng();
+        let key = RsaPrivateKey::new(&mut rng, 512).expect("generate test key");
+        let keyname = unique_keyname("convert_pem_to_sdk");
+        let pem_path = format!("{}.pem", keyname);
+        std::fs::write(&pem_path, key.to_pkcs1_pem(LineEnding::LF).unwrap().as_bytes()).unwrap();
+
+        pem_to_sdk(&keyname).expect("pem_to_sdk");
+
+        let loaded = sdk::load_private(&keyname).expect("load_private");
+        assert_eq!(loaded.n(), key.n());
+        assert_eq!(loaded.d(), key.d());
+
+        let _ = std::fs::remove_file(&pem_path);
+        let _ = std::fs::remove_file(format!("{}.pri", keyname));
+        let _ = std::fs::remove_file(format!("{}.pub", keyname));
+    }
+
+    #[test]
+    fn sdk_to_pem_round_trip() {
+        let mut rng = rand::thread_rng();
+        let key = RsaPrivateKey::new(&mut rng, 512).expect("generate test key");
+        let keyname = unique_keyname("convert_sdk_to_pem");
+        std::fs::write(
+            format!("{}.pri", keyname),
+            sdk::encode_sdk(sdk::private_label(), key.d(), key.n()),
+        )
+        .unwrap();
+        std::fs::write(
+            format!("{}.pub", keyname),
+            sdk::encode_public_sdk(key.e(), key.n()),
+        )
+        .unwrap();
+
+        for to in [ConvertTo::Pkcs1, ConvertTo::Pkcs8] {
+            sdk_to_pem(&keyname, to, false, true).expect("sdk_to_pem private");
+            let pem = std::fs::read_to_string(format!("{}.pem", keyname)).unwrap();
+            let parsed = match to {
+                ConvertTo::Pkcs1 => RsaPrivateKey::from_pkcs1_pem(&pem).expect("parse pkcs1 private pem"),
+                ConvertTo::Pkcs8 => RsaPrivateKey::from_pkcs8_pem(&pem).expect("parse pkcs8 private pem"),
+                ConvertTo::Sdk => unreachable!(),
+            };
+            assert_eq!(parsed.n(), key.n());
+            assert_eq!(parsed.d(), key.d());
+
+            sdk_to_pem(&keyname, to, true, false).expect("sdk_to_pem public");
+            let pem = std::fs::read_to_string(format!("{}.pem", keyname)).unwrap();
+            let parsed = match to {
+                ConvertTo::Pkcs1 => RsaPublicKey::from_pkcs1_pem(&pem).expect("parse pkcs1 public pem"),
+                ConvertTo::Pkcs8 => RsaPublicKey::from_public_key_pem(&pem).expect("parse pkcs8 public pem"),
+                ConvertTo::Sdk => unreachable!(),
+            };
+            assert_eq!(parsed.n(), key.n());
+        }
+
+        assert!(sdk_to_pem(&keyname, ConvertTo::Pkcs1, false, false).is_err());
+        assert!(sdk_to_pem(&keyname, ConvertTo::Pkcs1, true, true).is_err());
+
+        let _ = std::fs::remove_file(format!("{}.pem", keyname));
+        let _ = std::fs::remove_file(format!("{}.pri", keyname));
+        let _ = std::fs::remove_file(format!("{}.pub", keyname));
+    }
+}
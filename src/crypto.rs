@@ -0,0 +1,145 @@
+//! Sign/verify and encrypt/decrypt using keys stored in the SDK format.
+//! Signatures use PKCS#1 v1.5 over a SHA-256 digest; encryption uses
+//! PKCS#1 v1.5 padding, matching the scheme the `rsa` crate names
+//! `Pkcs1v15Sign` / `Pkcs1v15Encrypt`.
+
+use anyhow::{Context, Result};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use sha2::{Digest, Sha256};
+use rsa::traits::PublicKeyParts;
+use rsa::{Pkcs1v15Encrypt, Pkcs1v15Sign};
+
+use crate::sdk;
+
+pub fn sign(keyname: &str, file: &str) -> Result<()> {
+    let key = sdk::load_private(keyname)?;
+    let data = std::fs::read(file)?;
+    let hashed = Sha256::digest(&data);
+
+    let signature = key.sign(Pkcs1v15Sign::new::<Sha256>(), &hashed)?;
+    let sig_path = format!("{}.sig", file);
+    std::fs::write(&sig_path, BASE64.encode(signature))?;
+    println!("Wrote signature: {}", sig_path);
+    Ok(())
+}
+
+pub fn verify(keyname: &str, file: &str, sig: &str) -> Result<()> {
+    let key = sdk::load_public(keyname)?;
+    let data = std::fs::read(file)?;
+    let hashed = Sha256::digest(&data);
+
+    let signature = BASE64.decode(std::fs::read_to_string(sig)?.trim())?;
+
+    key.verify(Pkcs1v15Sign::new::<Sha256>(), &hashed, &signature)
+        .context("signature is invalid")?;
+    println!("Signature valid");
+    Ok(())
+}
+
+pub fn encrypt(keyname: &str, file: &str) -> Result<()> {
+    let key = sdk::load_public(keyname)?;
+    let data = std::fs::read(file)?;
+
+    let max_len = key.size().saturating_sub(11);
+    if data.len() > max_len {
+        println!(
+            "Warning: payload is {} bytes but this key can only encrypt up to {} bytes with PKCS#1 v1.5 padding",
+            data.len(),
+            max_len
+        );
+    }
+
+    let mut rng = rand::thread_rng();
+    let ciphertext = key.encrypt(&mut rng, Pkcs1v15Encrypt, &data)?;
+    let out_path = format!("{}.enc", file);
+    std::fs::write(&out_path, BASE64.encode(ciphertext))?;
+    println!("Wrote ciphertext: {}", out_path);
+    Ok(())
+}
+
+pub fn decrypt(keyname: &str, file: &str) -> Result<()> {
+    let key = sdk::load_private(keyname)?;
+    let ciphertext = BASE64.decode(std::fs::read_to_string(file)?.trim())?;
+
+    let plaintext = key.decrypt(Pkcs1v15Encrypt, &ciphertext)?;
+    let out_path = file.strip_suffix(".enc").unwrap_or(file).to_string() + ".dec";
+    std::fs::write(&out_path, plaintext)?;
+    println!("Wrote plaintext: {}", out_path);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::unique_keyname;
+    use rsa::traits::PrivateKeyParts;
+    use rsa::RsaPrivateKey;
+
+    fn write_test_key(keyname: &str) {
+        let mut rng = rand::thread_rng();
+        let key = RsaPrivateKey::new(&mut rng, 512).expect("generate test key");
+        std::fs::write(
+            format!("{}.pri", keyname),
+            sdk::encode_sdk(sdk::private_label(), key.d(), key.n()),
+        )
+        .unwrap();
+        std::fs::write(
+            format!("{}.pub", keyname),
+            sdk::encode_public_sdk(key.e(), key.n()),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn sign_then_verify_round_trip() {
+        let keyname = unique_keyname("crypto_sign");
+        write_test_key(&keyname);
+        let file_path = format!("{}.msg", keyname);
+        std::fs::write(&file_path, b"hello stacks").unwrap();
+
+        sign(&keyname, &file_path).expect("sign");
+        verify(&keyname, &file_path, &format!("{}.sig", file_path)).expect("verify should accept a valid signature");
+
+        let _ = std::fs::remove_file(&file_path);
+        let _ = std::fs::remove_file(format!("{}.sig", file_path));
+        let _ = std::fs::remove_file(format!("{}.pri", keyname));
+        let _ = std::fs::remove_file(format!("{}.pub", keyname));
+    }
+
+    #[test]
+    fn verify_rejects_tampered_file() {
+        let keyname = unique_keyname("crypto_verify_tamper");
+        write_test_key(&keyname);
+        let file_path = format!("{}.msg", keyname);
+        std::fs::write(&file_path, b"hello stacks").unwrap();
+
+        sign(&keyname, &file_path).expect("sign");
+        std::fs::write(&file_path, b"hello stacks, tampered").unwrap();
+        assert!(verify(&keyname, &file_path, &format!("{}.sig", file_path)).is_err());
+
+        let _ = std::fs::remove_file(&file_path);
+        let _ = std::fs::remove_file(format!("{}.sig", file_path));
+        let _ = std::fs::remove_file(format!("{}.pri", keyname));
+        let _ = std::fs::remove_file(format!("{}.pub", keyname));
+    }
+
+    #[test]
+    fn encrypt_then_decrypt_round_trip() {
+        let keyname = unique_keyname("crypto_encrypt");
+        write_test_key(&keyname);
+        let file_path = format!("{}.msg", keyname);
+        std::fs::write(&file_path, b"hello stacks").unwrap();
+
+        encrypt(&keyname, &file_path).expect("encrypt");
+        decrypt(&keyname, &format!("{}.enc", file_path)).expect("decrypt");
+
+        let decrypted = std::fs::read(format!("{}.dec", file_path)).unwrap();
+        assert_eq!(decrypted, b"hello stacks");
+
+        let _ = std::fs::remove_file(&file_path);
+        let _ = std::fs::remove_file(format!("{}.enc", file_path));
+        let _ = std::fs::remove_file(format!("{}.dec", file_path));
+        let _ = std::fs::remove_file(format!("{}.pri", keyname));
+        let _ = std::fs::remove_file(format!("{}.pub", keyname));
+    }
+}
@@ -0,0 +1,84 @@
+//! Mastodon-style Magic Public Key encoding, for publishing SDK keys on
+//! federated/Stacks identity endpoints (ActivityPub, `acct:` webfinger, ...).
+//!
+//! A Magic Public Key is `RSA.<b64url(n)>.<b64url(e)>`, where `n`/`e` are
+//! encoded as their minimal big-endian byte representation, usually
+//! prefixed with `data:application/magic-public-key,` when embedded in a
+//! webfinger document.
+
+use anyhow::{Context, Result};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD as BASE64URL, Engine as _};
+use rsa::BigUint;
+
+use crate::sdk;
+
+const SCHEME_PREFIX: &str = "data:application/magic-public-key,";
+
+/// Read `keyname.pub` and render it as a Magic Public Key string.
+pub fn to_magic_key(keyname: &str) -> Result<String> {
+    let (e, n) = sdk::read_sdk_file(keyname, false)?;
+    Ok(format!("RSA.{}.{}", b64(&n), b64(&e)))
+}
+
+/// Parse a Magic Public Key (with or without the `data:` scheme prefix)
+/// and write it back out as an SDK `.pub` file.
+pub fn from_magic_key(keyname: &str, magic: &str) -> Result<()> {
+    let body = magic.strip_prefix(SCHEME_PREFIX).unwrap_or(magic);
+    let body = body
+        .strip_prefix("RSA.")
+        .context("not a Magic Public Key (missing 'RSA.' prefix)")?;
+
+    let mut parts = body.splitn(2, '.');
+    let n = unb64(parts.next().context("Magic Public Key missing modulus")?)?;
+    let e = unb64(parts.next().context("Magic Public Key missing exponent")?)?;
+
+    let public_key = sdk::encode_public_sdk(&e, &n);
+    std::fs::write(format!("{}.pub", keyname), public_key)?;
+    println!("Wrote SDK public key: {}.pub", keyname);
+    Ok(())
+}
+
+fn b64(n: &BigUint) -> String {
+    BASE64URL.encode(n.to_bytes_be())
+}
+
+fn unb64(s: &str) -> Result<BigUint> {
+    let bytes = BASE64URL
+        .decode(s)
+        .context("invalid base64url in Magic Public Key")?;
+    Ok(BigUint::from_bytes_be(&bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::unique_keyname;
+    use rsa::traits::PublicKeyParts;
+    use rsa::RsaPrivateKey;
+
+    #[test]
+    fn magic_key_round_trip() {
+        let mut rng = rand::thread_rng();
+        let key = RsaPrivateKey::new(&mut rng, 512).expect("generate test key");
+
+        let keyname = unique_keyname("magic_export");
+        std::fs::write(
+            format!("{}.pub", keyname),
+            sdk::encode_public_sdk(key.e(), key.n()),
+        )
+        .unwrap();
+
+        let magic = to_magic_key(&keyname).expect("to_magic_key");
+        assert!(magic.starts_with("RSA."));
+
+        let imported_keyname = unique_keyname("magic_import");
+        from_magic_key(&imported_keyname, &magic).expect("from_magic_key");
+
+        let original = sdk::read_sdk_file(&keyname, false).unwrap();
+        let imported = sdk::read_sdk_file(&imported_keyname, false).unwrap();
+        assert_eq!(original, imported);
+
+        let _ = std::fs::remove_file(format!("{}.pub", keyname));
+        let _ = std::fs::remove_file(format!("{}.pub", imported_keyname));
+    }
+}
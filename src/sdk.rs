@@ -0,0 +1,211 @@
+//! Helpers for the tool's own key format: a PEM-shaped wrapper around a
+//! base64 blob of `d,n` (private) or `e,n` (public), where `d`/`e`/`n` are
+//! lowercase hex strings.
+
+use anyhow::{bail, Context, Result};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use rsa::{BigUint, RsaPrivateKey, RsaPublicKey};
+
+/// Public exponent used for every key this tool generates. Private SDK
+/// blobs only store `d,n`, so callers that need `e` back (e.g. to rebuild
+/// a full `RsaPrivateKey`) assume this default.
+pub const DEFAULT_PUBLIC_EXPONENT: u32 = 0x10001;
+
+/// Render a `BigUint` as lowercase hex, left-padded to an even number of
+/// digits so it matches the byte-pair grouping the SDK blobs expect.
+pub fn hex_even(n: &BigUint) -> String {
+    let hex = n.to_str_radix(16);
+    if hex.len().is_multiple_of(2) {
+        hex
+    } else {
+        format!("0{}", hex)
+    }
+}
+
+/// Render a `BigUint` as lowercase hex with no padding. Used for the
+/// public exponent, which the SDK format documents as the bare literal
+/// `10001` rather than the even-padded `010001` `hex_even` would produce.
+pub fn hex_minimal(n: &BigUint) -> String {
+    n.to_str_radix(16)
+}
+
+/// Parse a hex string (as produced by `hex_even`) back into a `BigUint`.
+pub fn parse_hex(s: &str) -> Result<BigUint> {
+    BigUint::parse_bytes(s.trim().as_bytes(), 16).context("invalid hex value in SDK blob")
+}
+
+/// Wrap a base64 blob in the tool's PEM-shaped envelope.
+pub fn wrap(label: &str, blob: &str) -> String {
+    format!("-----BEGIN {label}-----\n{blob}\n-----END {label}-----")
+}
+
+/// Strip the `-----BEGIN ...-----` / `-----END ...-----` wrapper (if
+/// present) and return the base64 payload.
+pub fn unwrap(contents: &str) -> String {
+    contents
+        .lines()
+        .filter(|line| !line.starts_with("-----"))
+        .collect::<Vec<_>>()
+        .join("")
+}
+
+/// Decode an SDK blob (`base64("first,n")`) into its two hex components.
+pub fn decode_blob(contents: &str) -> Result<(String, String)> {
+    let decoded = BASE64
+        .decode(unwrap(contents).trim())
+        .context("SDK blob is not valid base64")?;
+    let decoded = String::from_utf8(decoded).context("SDK blob is not valid UTF-8")?;
+    let mut parts = decoded.splitn(2, ',');
+    let first = parts.next().context("SDK blob missing first component")?;
+    let n = parts.next().context("SDK blob missing modulus")?;
+    Ok((first.to_string(), n.to_string()))
+}
+
+/// Read and decode an SDK `.pri` or `.pub` file for `keyname`.
+pub fn read_sdk_file(keyname: &str, private: bool) -> Result<(BigUint, BigUint)> {
+    let ext = if private { "pri" } else { "pub" };
+    let path = format!("{}.{}", keyname, ext);
+    let contents = std::fs::read_to_string(&path).with_context(|| format!("reading {}", path))?;
+    let (first, n) = decode_blob(&contents)?;
+    Ok((parse_hex(&first)?, parse_hex(&n)?))
+}
+
+/// Encode `(first, n)` hex components into the SDK blob format and wrap it
+/// with the appropriate PEM-shaped label.
+pub fn encode_sdk(label: &str, first: &BigUint, n: &BigUint) -> String {
+    let blob = BASE64.encode(format!("{},{}", hex_even(first), hex_even(n)).as_bytes());
+    wrap(label, &blob)
+}
+
+/// Encode `(e, n)` as the SDK public blob. `e` is left unpadded (`10001`,
+/// not `010001`) to match the exact literal the SDK format documents.
+pub fn encode_public_sdk(e: &BigUint, n: &BigUint) -> String {
+    let blob = BASE64.encode(format!("{},{}", hex_minimal(e), hex_even(n)).as_bytes());
+    wrap(public_label(), &blob)
+}
+
+/// Confirm a key's public exponent matches the one the SDK private format
+/// assumes. The `.pri` blob only stores `d,n`; `load_private` always refills
+/// `e` with `DEFAULT_PUBLIC_EXPONENT`, so a private key with any other
+/// exponent would come back silently wrong (or fail to recover its primes
+/// at all). Callers that turn a foreign private key into an SDK blob must
+/// check this before writing it out.
+pub fn require_default_exponent(e: &BigUint) -> Result<()> {
+    let default = BigUint::from(DEFAULT_PUBLIC_EXPONENT);
+    if e != &default {
+        bail!(
+            "private key uses public exponent 0x{} but the SDK private format only supports e = 0x{}; \
+             refusing to write a .pri blob that would load back as the wrong key",
+            e.to_str_radix(16),
+            default.to_str_radix(16)
+        );
+    }
+    Ok(())
+}
+
+/// Reconstruct a full `RsaPrivateKey` from `keyname.pri`, recovering the
+/// prime factors since the SDK blob only stores `d,n`.
+pub fn load_private(keyname: &str) -> Result<RsaPrivateKey> {
+    let (d, n) = read_sdk_file(keyname, true)?;
+    let e = BigUint::from(DEFAULT_PUBLIC_EXPONENT);
+    let (p, q) = recover_primes(&n, &e, &d)?;
+    RsaPrivateKey::from_components(n, e, d, vec![p, q])
+        .context("reconstructed RSA private key is invalid")
+}
+
+/// Reconstruct an `RsaPublicKey` from `keyname.pub`.
+pub fn load_public(keyname: &str) -> Result<RsaPublicKey> {
+    let (e, n) = read_sdk_file(keyname, false)?;
+    RsaPublicKey::new(n, e).context("reconstructed RSA public key is invalid")
+}
+
+pub fn private_label() -> &'static str {
+    "RSA PRIVATE KEY"
+}
+
+pub fn public_label() -> &'static str {
+    "RSA PUBLIC KEY"
+}
+
+/// Recover the prime factors `p, q` of `n` given the public/private
+/// exponent pair, following the probabilistic algorithm in the Handbook of
+/// Applied Cryptography (8.2.2): `e*d - 1` is a multiple of `lambda(n)`, so
+/// repeatedly halving it and looking for a non-trivial square root of unity
+/// modulo `n` exposes a factor.
+pub fn recover_primes(n: &BigUint, e: &BigUint, d: &BigUint) -> Result<(BigUint, BigUint)> {
+    use num_integer::Integer;
+    use num_traits::{One, Zero};
+    use rand::RngCore;
+
+    let one = BigUint::one();
+    let two = BigUint::from(2u32);
+
+    let k = e * d - &one;
+    let (mut t, mut m) = (0u32, k);
+    while (&m % &two).is_zero() {
+        m /= &two;
+        t += 1;
+    }
+
+    let byte_len = n.bits().div_ceil(8);
+    let mut rng = rand::thread_rng();
+    let mut buf = vec![0u8; byte_len];
+    for _ in 0..100 {
+        rng.fill_bytes(&mut buf);
+        let g = BigUint::from_bytes_be(&buf) % n;
+        if g.is_zero() {
+            continue;
+        }
+        let mut prev = g.modpow(&m, n);
+        for _ in 0..t {
+            let x = prev.modpow(&two, n);
+            if x == one && prev != one && prev != n - &one {
+                let candidate = (&prev - &one).gcd(n);
+                if !candidate.is_one() && &candidate != n {
+                    let other = n / &candidate;
+                    return Ok((candidate, other));
+                }
+            }
+            prev = x;
+        }
+    }
+
+    bail!("could not recover RSA primes from n, e, d")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsa::traits::{PrivateKeyParts, PublicKeyParts};
+
+    #[test]
+    fn hex_even_pads_odd_length() {
+        assert_eq!(hex_even(&BigUint::from(0xabcu32)), "0abc");
+    }
+
+    #[test]
+    fn hex_even_leaves_even_length_alone() {
+        assert_eq!(hex_even(&BigUint::from(0xabcdu32)), "abcd");
+    }
+
+    #[test]
+    fn encode_decode_sdk_round_trip() {
+        let mut rng = rand::thread_rng();
+        let key = RsaPrivateKey::new(&mut rng, 512).expect("generate test key");
+
+        let blob = encode_sdk(private_label(), key.d(), key.n());
+        let (d_hex, n_hex) = decode_blob(&blob).expect("decode blob");
+
+        assert_eq!(parse_hex(&d_hex).unwrap(), *key.d());
+        assert_eq!(parse_hex(&n_hex).unwrap(), *key.n());
+    }
+
+    #[test]
+    fn recover_primes_reconstructs_generated_key() {
+        let mut rng = rand::thread_rng();
+        let key = RsaPrivateKey::new(&mut rng, 512).expect("generate test key");
+
+        let (p, q) = recover_primes(key.n(), key.e(), key.d()).expect("recover primes");
+        assert_eq!(&p * &q, *key.n());
+    }
+}
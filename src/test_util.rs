@@ -0,0 +1,13 @@
+//! Shared helpers for this crate's unit tests.
+
+/// A temp-dir keyname unique to this process, so tests that write
+/// `.pri`/`.pub`/etc. files alongside each other don't collide when run
+/// concurrently.
+pub fn unique_keyname(tag: &str) -> String {
+    format!(
+        "{}/stacks_cli_test_{}_{}",
+        std::env::temp_dir().display(),
+        tag,
+        std::process::id()
+    )
+}